@@ -0,0 +1,115 @@
+use clap::{App, Arg};
+
+/// Build the clap command line interface.
+pub fn build() -> App<'static, 'static> {
+    App::new("lsd")
+        .about("An ls command with a lot of pretty colors and some other stuff.")
+        .arg(Arg::with_name("FILE").multiple(true).default_value("."))
+        .arg(Arg::with_name("all").short("a").long("all"))
+        .arg(Arg::with_name("almost-all").short("A").long("almost-all"))
+        .arg(
+            Arg::with_name("directory-only")
+                .short("d")
+                .long("directory-only"),
+        )
+        .arg(Arg::with_name("classic").long("classic"))
+        .arg(Arg::with_name("long").short("l").long("long"))
+        .arg(Arg::with_name("oneline").short("1").long("oneline"))
+        .arg(Arg::with_name("grid").long("grid"))
+        .arg(Arg::with_name("grid-details").long("grid-details"))
+        .arg(Arg::with_name("tree").long("tree"))
+        .arg(Arg::with_name("recursive").short("R").long("recursive"))
+        .arg(
+            Arg::with_name("depth")
+                .long("depth")
+                .takes_value(true)
+                .number_of_values(1),
+        )
+        .arg(Arg::with_name("reverse").short("r").long("reverse"))
+        .arg(Arg::with_name("timesort").short("t").long("timesort"))
+        .arg(Arg::with_name("sizesort").short("S").long("sizesort"))
+        .arg(
+            Arg::with_name("changetimesort")
+                .short("c")
+                .long("changetimesort"),
+        )
+        .arg(
+            Arg::with_name("accesstimesort")
+                .short("u")
+                .long("accesstimesort"),
+        )
+        .arg(Arg::with_name("indicators").short("F").long("classify"))
+        .arg(Arg::with_name("no-symlink").long("no-symlink"))
+        .arg(Arg::with_name("total-size").long("total-size"))
+        .arg(Arg::with_name("extended").short("@").long("extended"))
+        .arg(Arg::with_name("git").long("git"))
+        .arg(Arg::with_name("git-ignore").long("git-ignore"))
+        .arg(
+            Arg::with_name("config")
+                .long("config")
+                .takes_value(true)
+                .number_of_values(1),
+        )
+        .arg(
+            Arg::with_name("theme")
+                .long("theme")
+                .takes_value(true)
+                .number_of_values(1),
+        )
+        .arg(
+            Arg::with_name("color")
+                .long("color")
+                .possible_values(&["always", "auto", "never"])
+                .default_value("auto")
+                .multiple(true)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("icon")
+                .long("icon")
+                .possible_values(&["always", "auto", "never"])
+                .default_value("auto")
+                .multiple(true)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("icon-theme")
+                .long("icon-theme")
+                .possible_values(&["fancy", "unicode"])
+                .default_value("fancy")
+                .multiple(true)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("size")
+                .long("size")
+                .possible_values(&["default", "short", "bytes"])
+                .default_value("default")
+                .multiple(true)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("date")
+                .long("date")
+                .possible_values(&["date", "relative", "modified", "accessed", "changed"])
+                .default_value("date")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("group-dirs")
+                .long("group-dirs")
+                .possible_values(&["none", "first", "last"])
+                .default_value("none")
+                .multiple(true)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("blocks")
+                .long("blocks")
+                .possible_values(&["permission", "user", "group", "size", "date", "name", "git"])
+                .default_value("permission,user,group,size,date,name")
+                .use_delimiter(true)
+                .multiple(true)
+                .takes_value(true),
+        )
+}
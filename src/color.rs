@@ -0,0 +1,157 @@
+use std::collections::HashMap;
+
+use ansi_term::{ANSIString, Colour, Style};
+
+use crate::theme::ColorConfig;
+
+/// Which compiled-in palette to start from.
+#[derive(Clone, Debug, Copy, PartialEq, Eq)]
+pub enum Theme {
+    NoColor,
+    Default,
+}
+
+/// A colorable element of the listing.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Hash)]
+pub enum Elem {
+    Dir,
+    SymLink,
+    BrokenSymLink,
+    Read,
+    Write,
+    Exec,
+    NoAccess,
+    User,
+    Group,
+    Size,
+    Date,
+    GitNew,
+    GitModified,
+    GitDeleted,
+    GitRenamed,
+    GitTypechange,
+    GitIgnored,
+    GitConflicted,
+    GitClean,
+}
+
+/// The resolved color table. `None` when colors are disabled, in which case
+/// every element paints with the terminal's default style.
+pub struct Colors {
+    colors: Option<HashMap<Elem, Colour>>,
+}
+
+impl Colors {
+    /// Build the table from the compiled-in defaults only.
+    pub fn new(theme: Theme) -> Self {
+        Self::with_config(theme, &ColorConfig::default())
+    }
+
+    /// Build the table from the compiled-in defaults, then apply any overrides
+    /// parsed from the user's config (or seeded from `LS_COLORS`). A missing or
+    /// unparseable override leaves the default untouched.
+    pub fn with_config(theme: Theme, config: &ColorConfig) -> Self {
+        let colors = match theme {
+            Theme::NoColor => None,
+            Theme::Default => {
+                let mut map = default_colors();
+                apply_override(&mut map, Elem::Dir, &config.directory);
+                apply_override(&mut map, Elem::SymLink, &config.symlink);
+                apply_override(&mut map, Elem::BrokenSymLink, &config.broken_symlink);
+                apply_override(&mut map, Elem::User, &config.user);
+                apply_override(&mut map, Elem::Group, &config.group);
+                apply_override(&mut map, Elem::Size, &config.size);
+                apply_override(&mut map, Elem::Date, &config.date);
+                // The permission override recolors the whole permission string.
+                apply_override(&mut map, Elem::Read, &config.permission);
+                apply_override(&mut map, Elem::Write, &config.permission);
+                apply_override(&mut map, Elem::Exec, &config.permission);
+                Some(map)
+            }
+        };
+
+        Self { colors }
+    }
+
+    /// Paint `input` with the color configured for `elem`.
+    pub fn colorize(&self, input: String, elem: &Elem) -> ANSIString<'static> {
+        match self.colors {
+            Some(ref colors) => match colors.get(elem) {
+                Some(color) => color.paint(input),
+                None => Style::default().paint(input),
+            },
+            None => Style::default().paint(input),
+        }
+    }
+}
+
+/// The compiled-in default palette, matching the original hardcoded colors.
+fn default_colors() -> HashMap<Elem, Colour> {
+    let mut map = HashMap::new();
+    map.insert(Elem::Dir, Colour::Fixed(33));
+    map.insert(Elem::SymLink, Colour::Fixed(44));
+    map.insert(Elem::BrokenSymLink, Colour::Fixed(124));
+    map.insert(Elem::Read, Colour::Fixed(40));
+    map.insert(Elem::Write, Colour::Fixed(192));
+    map.insert(Elem::Exec, Colour::Fixed(124));
+    map.insert(Elem::NoAccess, Colour::Fixed(245));
+    map.insert(Elem::User, Colour::Fixed(187));
+    map.insert(Elem::Group, Colour::Fixed(7));
+    map.insert(Elem::Size, Colour::Fixed(34));
+    map.insert(Elem::Date, Colour::Fixed(40));
+    map.insert(Elem::GitNew, Colour::Fixed(40));
+    map.insert(Elem::GitModified, Colour::Fixed(192));
+    map.insert(Elem::GitDeleted, Colour::Fixed(124));
+    map.insert(Elem::GitRenamed, Colour::Fixed(42));
+    map.insert(Elem::GitTypechange, Colour::Fixed(136));
+    map.insert(Elem::GitIgnored, Colour::Fixed(245));
+    map.insert(Elem::GitConflicted, Colour::Fixed(160));
+    map.insert(Elem::GitClean, Colour::Fixed(245));
+    map
+}
+
+fn apply_override(map: &mut HashMap<Elem, Colour>, elem: Elem, value: &Option<String>) {
+    if let Some(raw) = value {
+        if let Some(color) = parse_color(raw) {
+            map.insert(elem, color);
+        }
+    }
+}
+
+/// Parse either a color name (`blue`), a 256-color index (`208`), or an SGR
+/// sequence as found in `LS_COLORS` (`01;34`), returning the foreground color.
+/// A bare number in the 30-37 or 90-97 range is read as an SGR code.
+fn parse_color(raw: &str) -> Option<Colour> {
+    match raw.to_ascii_lowercase().as_str() {
+        "black" => return Some(Colour::Black),
+        "red" => return Some(Colour::Red),
+        "green" => return Some(Colour::Green),
+        "yellow" => return Some(Colour::Yellow),
+        "blue" => return Some(Colour::Blue),
+        "purple" | "magenta" => return Some(Colour::Purple),
+        "cyan" => return Some(Colour::Cyan),
+        "white" => return Some(Colour::White),
+        _ => {}
+    }
+
+    // `LS_COLORS` values are `;`-separated SGR codes; the 30-37/90-97 range (or
+    // a trailing 256-color index after `38;5`) carries the foreground we care about.
+    let codes: Vec<&str> = raw.split(';').collect();
+    for (i, code) in codes.iter().enumerate() {
+        if *code == "38" && codes.get(i + 1) == Some(&"5") {
+            return codes
+                .get(i + 2)
+                .and_then(|n| n.parse::<u8>().ok())
+                .map(Colour::Fixed);
+        }
+        if let Ok(n) = code.parse::<u8>() {
+            match n {
+                30..=37 => return Some(Colour::Fixed(n - 30)),
+                90..=97 => return Some(Colour::Fixed(n - 90 + 8)),
+                _ => {}
+            }
+        }
+    }
+
+    raw.parse::<u8>().ok().map(Colour::Fixed)
+}
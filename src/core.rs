@@ -1,13 +1,17 @@
 use crate::color::{self, Colors};
 use crate::display;
 use crate::flags::{Display, Flags, IconTheme, Layout, WhenFlag};
+#[cfg(feature = "git")]
+use crate::flags::Block;
+#[cfg(feature = "git")]
+use crate::git::{GitCache, GitIgnore};
 use crate::icon::{self, Icons};
 use crate::meta::Meta;
 use crate::sort;
+use crate::theme::ThemeConfig;
 use std::path::PathBuf;
 use std::{fs, io};
 
-use super::libc;
 #[cfg(not(target_os = "windows"))]
 use std::os::unix::io::AsRawFd;
 
@@ -22,7 +26,7 @@ pub struct Core {
 }
 
 impl Core {
-    pub fn new(flags: Flags) -> Self {
+    pub fn new(mut flags: Flags) -> Self {
         // Check through libc if stdout is a tty. Unix specific so not on windows.
         // Determine color output availability (and initialize color output (for Windows 10))
         #[cfg(not(target_os = "windows"))]
@@ -37,8 +41,6 @@ impl Core {
         #[cfg(target_os = "windows")]
         let console_color_ok = ansi_term::enable_ansi_support().is_ok();
 
-        let mut inner_flags = flags.clone();
-
         let color_theme = match (tty_available && console_color_ok, flags.color) {
             (_, WhenFlag::Never) | (false, WhenFlag::Auto) => color::Theme::NoColor,
             _ => color::Theme::Default,
@@ -54,15 +56,27 @@ impl Core {
             // The output is not a tty, this means the command is piped. (ex: lsd -l | less)
             //
             // Most of the programs does not handle correctly the ansi colors
-            // or require a raw output (like the `wc` command).
-            inner_flags.layout = Layout::OneLine { long: false };
+            // or require a raw output (like the `wc` command). Only the grid
+            // views need the terminal width to pack columns, so fall back to a
+            // plain listing for those — keeping the detailed columns for
+            // grid-details. One-line and tree layouts are left untouched.
+            flags.layout = match flags.layout {
+                Layout::GridDetails => Layout::OneLine { long: true },
+                Layout::Grid => Layout::OneLine { long: false },
+                other => other,
+            };
         };
 
+        // Load any user theme overrides before constructing the color and icon
+        // tables. Missing files or keys fall back to the compiled-in presets
+        // selected above, so behavior is unchanged without a config.
+        let theme_config = ThemeConfig::load(flags.config.as_deref());
+
         Self {
             flags,
             //display: Display::new(inner_flags),
-            colors: Colors::new(color_theme),
-            icons: Icons::new(icon_theme),
+            colors: Colors::with_config(color_theme, &theme_config.colors),
+            icons: Icons::with_config(icon_theme, &theme_config.icons),
         }
     }
 
@@ -87,7 +101,7 @@ impl Core {
                 continue;
             }
 
-            let mut meta = match Meta::from_path(&path) {
+            let mut meta = match Meta::from_path(&path, &self.flags) {
                 Ok(meta) => meta,
                 Err(err) => {
                     eprintln!("cannot access '{}': {}", path.display(), err);
@@ -100,9 +114,28 @@ impl Core {
                     meta_list.push(meta);
                 }
                 _ => {
-                    match meta.recurse_into(depth, self.flags.display) {
+                    // Discover the enclosing repository once per tree so that
+                    // `--git-ignore` can prune ignored entries before recursing
+                    // into them. The filter composes with the `Display`
+                    // visibility rules rather than replacing them.
+                    #[cfg(feature = "git")]
+                    let git_ignore = if self.flags.git_ignore {
+                        Some(GitIgnore::new(&meta.path))
+                    } else {
+                        None
+                    };
+
+                    #[cfg(feature = "git")]
+                    let recursed =
+                        meta.recurse_into(depth, &self.flags, git_ignore.as_ref());
+                    #[cfg(not(feature = "git"))]
+                    let recursed = meta.recurse_into(depth, &self.flags);
+
+                    match recursed {
                         Ok(content) => {
                             meta.content = content;
+                            #[cfg(feature = "git")]
+                            self.attach_git_status(&mut meta);
                             meta_list.push(meta);
                         }
                         Err(err) => {
@@ -122,6 +155,38 @@ impl Core {
         meta_list
     }
 
+    /// Populate the git status of `meta` and everything beneath it, building
+    /// one `GitCache` for the tree and reusing it for every directory the tree
+    /// spans. Only compiled when the `git` feature is enabled, and does nothing
+    /// unless the git-status block is requested.
+    #[cfg(feature = "git")]
+    fn attach_git_status(&self, meta: &mut Meta) {
+        if !self.flags.blocks.contains(&Block::GitStatus) {
+            return;
+        }
+
+        let cache = GitCache::new(&meta.path);
+        self.fill_git_status(meta, &cache);
+    }
+
+    #[cfg(feature = "git")]
+    fn fill_git_status(&self, meta: &mut Meta, cache: &GitCache) {
+        // Directories carry the rolled-up status of everything beneath them,
+        // whether or not the tree was deep enough to recurse into them; a
+        // regular file carries only its own.
+        meta.git_status = Some(if meta.file_type == crate::meta::FileType::Directory {
+            cache.get_dir(&meta.path)
+        } else {
+            cache.get(&meta.path)
+        });
+
+        if let Some(ref mut content) = meta.content {
+            for child in content.iter_mut() {
+                self.fill_git_status(child, cache);
+            }
+        }
+    }
+
     fn sort(&self, metas: &mut Vec<Meta>) {
         metas.sort_unstable_by(|a, b| sort::by_meta(a, b, &self.flags));
 
@@ -139,6 +204,9 @@ impl Core {
             }
             Layout::Tree { .. } => display::tree(metas, &self.flags, &self.colors, &self.icons),
             Layout::Grid => display::grid(metas, &self.flags, &self.colors, &self.icons),
+            Layout::GridDetails => {
+                display::grid_details(metas, &self.flags, &self.colors, &self.icons)
+            }
         };
         print!("{}", output);
     }
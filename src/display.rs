@@ -0,0 +1,358 @@
+use std::fs;
+
+use crate::color::{Colors, Elem};
+use crate::flags::{Block, Flags, Layout};
+use crate::icon::Icons;
+use crate::meta::{FileType, Meta};
+
+#[cfg(feature = "git")]
+use crate::git::{GitFileStatus, GitStatus};
+
+/// Spaces inserted between blocks within a long row and between grid columns.
+const PADDING: usize = 2;
+
+/// A rendered, already-colored string plus its visible (ANSI-stripped) width.
+struct Cell {
+    value: String,
+    width: usize,
+}
+
+impl Cell {
+    fn new(value: String, width: usize) -> Self {
+        Self { value, width }
+    }
+
+    /// Right-pad with spaces to at least `target` visible columns.
+    fn padded(&self, target: usize) -> String {
+        let mut out = self.value.clone();
+        if target > self.width {
+            out.push_str(&" ".repeat(target - self.width));
+        }
+        out
+    }
+}
+
+/// Plain single-line output, optionally in long format.
+pub fn one_line(metas: Vec<Meta>, flags: &Flags, colors: &Colors, icons: &Icons) -> String {
+    let entries = flatten(&metas);
+    let long = matches!(flags.layout, Layout::OneLine { long: true });
+
+    if !long {
+        let mut out = String::new();
+        for meta in &entries {
+            out.push_str(&name_cell(meta, flags, colors, icons).value);
+            out.push('\n');
+        }
+        return out;
+    }
+
+    let rows: Vec<Vec<Cell>> = entries
+        .iter()
+        .map(|meta| long_cells(meta, flags, colors, icons))
+        .collect();
+    let widths = column_widths(&rows);
+
+    let mut out = String::new();
+    for (meta, row) in entries.iter().zip(rows.iter()) {
+        out.push_str(&join_row(row, &widths));
+        out.push('\n');
+        out.push_str(&extended_lines(meta, flags));
+    }
+    out
+}
+
+/// Long rows packed into as many side-by-side columns as fit the terminal.
+pub fn grid_details(metas: Vec<Meta>, flags: &Flags, colors: &Colors, icons: &Icons) -> String {
+    let entries = flatten(&metas);
+    if entries.is_empty() {
+        return String::new();
+    }
+
+    // Render each entry's long row with aligned sub-columns, so every row is
+    // the same visible width and the grid search below only has to reason
+    // about that single width.
+    let rows: Vec<Vec<Cell>> = entries
+        .iter()
+        .map(|meta| long_cells(meta, flags, colors, icons))
+        .collect();
+    let widths = column_widths(&rows);
+    let row_width: usize = widths.iter().sum::<usize>() + PADDING * widths.len().saturating_sub(1);
+
+    let lines: Vec<String> = rows.iter().map(|row| join_row(row, &widths)).collect();
+
+    // Extended listings carry per-file continuation lines that cannot share a
+    // grid column, so fall back to one row per line in that mode.
+    let term_width = match terminal_width() {
+        Some(width) if !flags.extended => width,
+        _ => {
+            let mut out = String::new();
+            for (meta, line) in entries.iter().zip(lines.iter()) {
+                out.push_str(line);
+                out.push('\n');
+                out.push_str(&extended_lines(meta, flags));
+            }
+            return out;
+        }
+    };
+
+    // Search downward for the largest column count whose total width fits.
+    let mut columns = 1;
+    for candidate in (1..=lines.len()).rev() {
+        let total = candidate * row_width + PADDING * (candidate - 1);
+        if total <= term_width {
+            columns = candidate;
+            break;
+        }
+    }
+
+    let rows_count = lines.len().div_ceil(columns);
+    let mut out = String::new();
+    for r in 0..rows_count {
+        let mut line = String::new();
+        for c in 0..columns {
+            // Row-major fill, matching the existing grid code.
+            let idx = r * columns + c;
+            if idx >= lines.len() {
+                break;
+            }
+            if c > 0 {
+                line.push_str(&" ".repeat(PADDING));
+            }
+            line.push_str(&lines[idx]);
+            // join_row leaves the name block unpadded; pad it here so that
+            // every row spans `row_width` and the next column lines up.
+            let name_width = widths[widths.len() - 1];
+            if let Some(name) = rows[idx].last() {
+                line.push_str(&" ".repeat(name_width.saturating_sub(name.width)));
+            }
+        }
+        out.push_str(line.trim_end());
+        out.push('\n');
+    }
+    out
+}
+
+/// Name-only grid packed into columns that fill the terminal.
+pub fn grid(metas: Vec<Meta>, flags: &Flags, colors: &Colors, icons: &Icons) -> String {
+    let entries = flatten(&metas);
+    if entries.is_empty() {
+        return String::new();
+    }
+
+    let cells: Vec<Cell> = entries
+        .iter()
+        .map(|meta| name_cell(meta, flags, colors, icons))
+        .collect();
+    let term_width = terminal_width().unwrap_or(80);
+
+    let max = cells.iter().map(|c| c.width).max().unwrap_or(0) + PADDING;
+    let columns = (term_width / max).max(1);
+    let rows_count = cells.len().div_ceil(columns);
+
+    let mut out = String::new();
+    for r in 0..rows_count {
+        for c in 0..columns {
+            let idx = r * columns + c;
+            if idx >= cells.len() {
+                break;
+            }
+            out.push_str(&cells[idx].padded(max));
+        }
+        out.push('\n');
+    }
+    out
+}
+
+/// Recursive tree view.
+pub fn tree(metas: Vec<Meta>, flags: &Flags, colors: &Colors, icons: &Icons) -> String {
+    let mut out = String::new();
+    for meta in &metas {
+        tree_node(meta, flags, colors, icons, "", &mut out);
+    }
+    out
+}
+
+fn tree_node(meta: &Meta, flags: &Flags, colors: &Colors, icons: &Icons, prefix: &str, out: &mut String) {
+    out.push_str(prefix);
+    out.push_str(&name_cell(meta, flags, colors, icons).value);
+    out.push('\n');
+
+    if let Some(ref content) = meta.content {
+        let child_prefix = format!("{}  ", prefix);
+        for child in content {
+            tree_node(child, flags, colors, icons, &child_prefix, out);
+        }
+    }
+}
+
+/// Flatten the fetched metas into the entries to display: a directory argument
+/// expands to its contents, a file argument stays as itself.
+fn flatten(metas: &[Meta]) -> Vec<&Meta> {
+    let mut entries = Vec::new();
+    for meta in metas {
+        match meta.content {
+            Some(ref content) => entries.extend(content.iter()),
+            None => entries.push(meta),
+        }
+    }
+    entries
+}
+
+/// Per-block maximum widths across every row, for column alignment.
+fn column_widths(rows: &[Vec<Cell>]) -> Vec<usize> {
+    let count = rows.iter().map(|r| r.len()).max().unwrap_or(0);
+    let mut widths = vec![0; count];
+    for row in rows {
+        for (i, cell) in row.iter().enumerate() {
+            if cell.width > widths[i] {
+                widths[i] = cell.width;
+            }
+        }
+    }
+    widths
+}
+
+fn join_row(row: &[Cell], widths: &[usize]) -> String {
+    let mut line = String::new();
+    for (i, cell) in row.iter().enumerate() {
+        if i > 0 {
+            line.push_str(&" ".repeat(PADDING));
+        }
+        // The final block (the name) is not padded out.
+        if i + 1 == row.len() {
+            line.push_str(&cell.value);
+        } else {
+            line.push_str(&cell.padded(widths[i]));
+        }
+    }
+    line
+}
+
+/// Build the aligned sub-columns of an entry's long row, honoring `blocks`.
+fn long_cells(meta: &Meta, flags: &Flags, colors: &Colors, icons: &Icons) -> Vec<Cell> {
+    let mut cells = Vec::with_capacity(flags.blocks.len());
+    for block in &flags.blocks {
+        let cell = match block {
+            Block::Permission => permission_cell(meta, colors),
+            Block::User => {
+                let value = meta.owner.user.clone();
+                let width = value.chars().count();
+                Cell::new(colors.colorize(value, &Elem::User).to_string(), width)
+            }
+            Block::Group => {
+                let value = meta.owner.group.clone();
+                let width = value.chars().count();
+                Cell::new(colors.colorize(value, &Elem::Group).to_string(), width)
+            }
+            Block::Size => {
+                let value = meta.size.render(flags.size);
+                let width = value.chars().count();
+                Cell::new(colors.colorize(value, &Elem::Size).to_string(), width)
+            }
+            Block::Date => {
+                let value = meta.dates.render(flags.date);
+                let width = value.chars().count();
+                Cell::new(colors.colorize(value, &Elem::Date).to_string(), width)
+            }
+            Block::Name => name_cell(meta, flags, colors, icons),
+            Block::GitStatus => git_cell(meta, colors),
+        };
+        cells.push(cell);
+    }
+    cells
+}
+
+/// The permission string, with an `@` appended when the file carries extended
+/// attributes (matching `ls -l@`).
+fn permission_cell(meta: &Meta, colors: &Colors) -> Cell {
+    let perms = meta.permissions.render();
+    let mut width = perms.chars().count();
+    let mut value = colors.colorize(perms, &Elem::Read).to_string();
+    if meta.xattrs.has_attributes() {
+        value.push_str(&colors.colorize("@".to_string(), &Elem::NoAccess).to_string());
+        width += 1;
+    }
+    Cell::new(value, width)
+}
+
+fn name_cell(meta: &Meta, flags: &Flags, colors: &Colors, icons: &Icons) -> Cell {
+    let icon = icons.get(meta);
+    let mut text = format!("{}{}", icon, meta.name);
+
+    if flags.display_indicators && meta.file_type == FileType::Directory {
+        text.push('/');
+    }
+
+    if let Some(ref target) = meta.symlink_target {
+        text.push_str(&format!(" \u{21d2} {}", target.to_string_lossy()));
+    }
+
+    let width = text.chars().count();
+    let elem = name_elem(meta);
+    Cell::new(colors.colorize(text, &elem).to_string(), width)
+}
+
+fn name_elem(meta: &Meta) -> Elem {
+    match meta.file_type {
+        FileType::Directory => Elem::Dir,
+        FileType::SymLink => {
+            if fs::metadata(&meta.path).is_err() {
+                Elem::BrokenSymLink
+            } else {
+                Elem::SymLink
+            }
+        }
+        _ => Elem::NoAccess,
+    }
+}
+
+/// One continuation line per extended attribute, indented beneath the entry.
+fn extended_lines(meta: &Meta, flags: &Flags) -> String {
+    if !flags.extended {
+        return String::new();
+    }
+    let mut out = String::new();
+    for attr in meta.xattrs.iter() {
+        out.push_str(&format!("    {} ({} bytes)\n", attr.name, attr.size));
+    }
+    out
+}
+
+#[cfg(feature = "git")]
+fn git_cell(meta: &Meta, colors: &Colors) -> Cell {
+    let status = meta.git_status.unwrap_or(GitFileStatus {
+        staged: GitStatus::Unmodified,
+        unstaged: GitStatus::Unmodified,
+    });
+    let staged = colors
+        .colorize(status.staged.glyph().to_string(), &git_elem(status.staged))
+        .to_string();
+    let unstaged = colors
+        .colorize(status.unstaged.glyph().to_string(), &git_elem(status.unstaged))
+        .to_string();
+    Cell::new(format!("{}{}", staged, unstaged), 2)
+}
+
+#[cfg(not(feature = "git"))]
+fn git_cell(_meta: &Meta, _colors: &Colors) -> Cell {
+    // Without the `git` feature the column renders as the clean placeholder.
+    Cell::new("--".to_string(), 2)
+}
+
+#[cfg(feature = "git")]
+fn git_elem(status: GitStatus) -> Elem {
+    match status {
+        GitStatus::New => Elem::GitNew,
+        GitStatus::Modified => Elem::GitModified,
+        GitStatus::Deleted => Elem::GitDeleted,
+        GitStatus::Renamed => Elem::GitRenamed,
+        GitStatus::Typechange => Elem::GitTypechange,
+        GitStatus::Ignored => Elem::GitIgnored,
+        GitStatus::Conflicted => Elem::GitConflicted,
+        GitStatus::Unmodified => Elem::GitClean,
+    }
+}
+
+fn terminal_width() -> Option<usize> {
+    terminal_size::terminal_size().map(|(w, _)| w.0 as usize)
+}
@@ -1,4 +1,5 @@
 use clap::{ArgMatches, Error, ErrorKind};
+use std::path::PathBuf;
 
 #[derive(Clone, Debug)]
 pub struct Flags {
@@ -19,6 +20,9 @@ pub struct Flags {
     pub blocks: Vec<Block>,
     pub no_symlink: bool,
     pub total_size: bool,
+    pub extended: bool,
+    pub git_ignore: bool,
+    pub config: Option<PathBuf>,
 }
 
 impl Flags {
@@ -42,7 +46,11 @@ impl Flags {
             Display::DisplayOnlyVisible
         };
 
-        let sort_by = if matches.is_present("timesort") {
+        let sort_by = if matches.is_present("changetimesort") {
+            SortFlag::ChangeTime
+        } else if matches.is_present("accesstimesort") {
+            SortFlag::AccessTime
+        } else if matches.is_present("timesort") {
             SortFlag::Time
         } else if matches.is_present("sizesort") {
             SortFlag::Size
@@ -58,6 +66,10 @@ impl Flags {
             Layout::Tree {
                 long: matches.is_present("long"),
             }
+        } else if matches.is_present("grid-details")
+            || (matches.is_present("long") && matches.is_present("grid"))
+        {
+            Layout::GridDetails
         } else if matches.is_present("long") {
             Layout::OneLine { long: true }
         } else if matches.is_present("oneline") {
@@ -90,10 +102,23 @@ impl Flags {
                     ErrorKind::MissingRequiredArgument,
                 ));
             }
-            None => usize::max_value(),
+            None => usize::MAX,
         };
         let no_symlink = matches.is_present("no-symlink");
         let total_size = matches.is_present("total-size");
+        let extended = matches.is_present("extended");
+        let git_ignore = matches.is_present("git-ignore");
+        let config = matches
+            .value_of("config")
+            .or_else(|| matches.value_of("theme"))
+            .map(PathBuf::from);
+
+        let mut blocks: Vec<Block> = blocks_inputs.into_iter().map(Block::from).collect();
+        // `--git` is a shortcut for prepending the git-status column to the
+        // configured blocks, mirroring the way `--long` selects the long layout.
+        if matches.is_present("git") && !blocks.contains(&Block::GitStatus) {
+            blocks.insert(0, Block::GitStatus);
+        }
 
         Ok(Self {
             display,
@@ -105,10 +130,10 @@ impl Flags {
             sort_by,
             sort_order,
             size: SizeFlag::from(size_inputs[size_inputs.len() - 1]),
-            blocks: blocks_inputs.into_iter().map(|b| Block::from(b)).collect(),
+            blocks,
             // Take only the last value
             date: if classic_mode {
-                DateFlag::Date
+                DateFlag::date()
             } else {
                 DateFlag::from(date_inputs[date_inputs.len() - 1])
             },
@@ -130,6 +155,9 @@ impl Flags {
             },
             no_symlink,
             total_size,
+            extended,
+            git_ignore,
+            config,
         })
     }
 }
@@ -142,12 +170,12 @@ impl Default for Flags {
             long_mode: false,
             display_indicators: false,
             recursive: false,
-            recursion_depth: usize::max_value(),
+            recursion_depth: usize::MAX,
             sort_by: SortFlag::Name,
             sort_order: SortOrder::Default,
             directory_order: DirOrderFlag::None,
             size: SizeFlag::Default,
-            date: DateFlag::Date,
+            date: DateFlag::date(),
             color: WhenFlag::Auto,
             icon: WhenFlag::Auto,
             icon_theme: IconTheme::Fancy,
@@ -161,6 +189,9 @@ impl Default for Flags {
             ],
             no_symlink: false,
             total_size: false,
+            extended: false,
+            git_ignore: false,
+            config: None,
         }
     }
 }
@@ -174,6 +205,7 @@ pub enum Block {
     Size,
     Date,
     Name,
+    GitStatus,
 }
 impl<'a> From<&'a str> for Block {
     fn from(block: &'a str) -> Self {
@@ -185,6 +217,7 @@ impl<'a> From<&'a str> for Block {
             "size" => Block::Size,
             "date" => Block::Date,
             "name" => Block::Name,
+            "git" => Block::GitStatus,
             _ => panic!("invalid \"time\" flag: {}", block),
         }
     }
@@ -216,17 +249,50 @@ impl<'a> From<&'a str> for SizeFlag {
     }
 }
 
+/// Which of the three timestamps the Date block renders.
 #[derive(Clone, Debug, Copy, PartialEq, Eq)]
-pub enum DateFlag {
-    Date,
-    Relative,
+pub enum DateField {
+    Modified,
+    Accessed,
+    Changed,
+}
+
+/// How the Date block is rendered: which timestamp to show, and whether to
+/// format it relatively (e.g. "2 days ago"). The relative toggle is orthogonal
+/// to the field selection.
+#[derive(Clone, Debug, Copy, PartialEq, Eq)]
+pub struct DateFlag {
+    pub field: DateField,
+    pub relative: bool,
+}
+
+impl DateFlag {
+    /// The historical default: absolute modified time. Also what `--classic`
+    /// pins the Date block to.
+    pub const fn date() -> Self {
+        Self {
+            field: DateField::Modified,
+            relative: false,
+        }
+    }
 }
 
 impl<'a> From<&'a str> for DateFlag {
     fn from(time: &'a str) -> Self {
         match time {
-            "date" => DateFlag::Date,
-            "relative" => DateFlag::Relative,
+            "date" | "modified" => DateFlag::date(),
+            "relative" => DateFlag {
+                field: DateField::Modified,
+                relative: true,
+            },
+            "accessed" => DateFlag {
+                field: DateField::Accessed,
+                relative: false,
+            },
+            "changed" => DateFlag {
+                field: DateField::Changed,
+                relative: false,
+            },
             _ => panic!("invalid \"time\" flag: {}", time),
         }
     }
@@ -255,6 +321,8 @@ impl<'a> From<&'a str> for WhenFlag {
 pub enum SortFlag {
     Name,
     Time,
+    ChangeTime,
+    AccessTime,
     Size,
 }
 
@@ -301,13 +369,14 @@ impl<'a> From<&'a str> for IconTheme {
 #[derive(Clone, Debug, Copy, PartialEq, Eq)]
 pub enum Layout {
     Grid,
+    GridDetails,
     Tree { long: bool },
     OneLine { long: bool },
 }
 
 #[cfg(test)]
 mod test {
-    use super::Flags;
+    use super::{Block, DateField, DateFlag, Flags, Layout, SortFlag};
     use crate::app;
     use clap::ErrorKind;
 
@@ -332,4 +401,76 @@ mod test {
         assert!(res.is_err());
         assert_eq!(res.unwrap_err().kind, ErrorKind::MissingRequiredArgument);
     }
+
+    #[test]
+    fn test_block_from_git() {
+        assert_eq!(Block::from("git"), Block::GitStatus);
+    }
+
+    #[test]
+    fn test_dateflag_from_selects_field_and_relative() {
+        assert_eq!(DateFlag::from("date"), DateFlag::date());
+        assert_eq!(DateFlag::from("modified"), DateFlag::date());
+        assert_eq!(
+            DateFlag::from("relative"),
+            DateFlag {
+                field: DateField::Modified,
+                relative: true,
+            }
+        );
+        assert_eq!(
+            DateFlag::from("accessed"),
+            DateFlag {
+                field: DateField::Accessed,
+                relative: false,
+            }
+        );
+        assert_eq!(
+            DateFlag::from("changed"),
+            DateFlag {
+                field: DateField::Changed,
+                relative: false,
+            }
+        );
+    }
+
+    #[test]
+    fn test_sort_by_change_time() {
+        let matches = app::build()
+            .get_matches_from_safe(vec!["lsd", "-c"])
+            .unwrap();
+        let flags = Flags::from_matches(&matches).unwrap();
+
+        assert_eq!(flags.sort_by, SortFlag::ChangeTime);
+    }
+
+    #[test]
+    fn test_sort_by_access_time() {
+        let matches = app::build()
+            .get_matches_from_safe(vec!["lsd", "-u"])
+            .unwrap();
+        let flags = Flags::from_matches(&matches).unwrap();
+
+        assert_eq!(flags.sort_by, SortFlag::AccessTime);
+    }
+
+    #[test]
+    fn test_grid_details_explicit_flag() {
+        let matches = app::build()
+            .get_matches_from_safe(vec!["lsd", "--grid-details"])
+            .unwrap();
+        let flags = Flags::from_matches(&matches).unwrap();
+
+        assert_eq!(flags.layout, Layout::GridDetails);
+    }
+
+    #[test]
+    fn test_grid_details_from_long_and_grid() {
+        let matches = app::build()
+            .get_matches_from_safe(vec!["lsd", "--long", "--grid"])
+            .unwrap();
+        let flags = Flags::from_matches(&matches).unwrap();
+
+        assert_eq!(flags.layout, Layout::GridDetails);
+    }
 }
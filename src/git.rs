@@ -0,0 +1,322 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use git2::{Repository, Status, StatusOptions};
+
+/// One half of a file's VCS status. The same set of states describes both the
+/// staged (index vs HEAD) and unstaged (workdir vs index) columns, so a single
+/// enum serves both and the renderer shares one glyph/rank table.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GitStatus {
+    Unmodified,
+    New,
+    Modified,
+    Deleted,
+    Renamed,
+    Typechange,
+    Ignored,
+    Conflicted,
+}
+
+impl GitStatus {
+    /// Severity used when rolling a directory up to its "worst" child. Clean
+    /// and ignored entries rank lowest so a directory full of build artifacts
+    /// does not mask real changes beneath it.
+    fn rank(self) -> u8 {
+        match self {
+            GitStatus::Unmodified | GitStatus::Ignored => 0,
+            GitStatus::New => 1,
+            GitStatus::Modified => 2,
+            GitStatus::Deleted => 3,
+            GitStatus::Renamed => 4,
+            GitStatus::Typechange => 5,
+            GitStatus::Conflicted => 6,
+        }
+    }
+
+    /// The single-character glyph shown in the git-status column.
+    pub fn glyph(self) -> char {
+        match self {
+            GitStatus::Unmodified => '-',
+            GitStatus::New => 'N',
+            GitStatus::Modified => 'M',
+            GitStatus::Deleted => 'D',
+            GitStatus::Renamed => 'R',
+            GitStatus::Typechange => 'T',
+            GitStatus::Ignored => 'I',
+            GitStatus::Conflicted => 'U',
+        }
+    }
+}
+
+/// The two-character VCS status of a single path: the staged glyph followed by
+/// the unstaged glyph, the way `exa --git` renders it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GitFileStatus {
+    pub staged: GitStatus,
+    pub unstaged: GitStatus,
+}
+
+impl GitFileStatus {
+    fn clean() -> Self {
+        Self {
+            staged: GitStatus::Unmodified,
+            unstaged: GitStatus::Unmodified,
+        }
+    }
+
+    /// Fold `other` into `self`, keeping the more severe status of each half.
+    /// Used to roll a directory up to the worst status among its contents.
+    fn merge_worst(&mut self, other: GitFileStatus) {
+        if other.staged.rank() > self.staged.rank() {
+            self.staged = other.staged;
+        }
+        if other.unstaged.rank() > self.unstaged.rank() {
+            self.unstaged = other.unstaged;
+        }
+    }
+
+    fn from_bits(status: Status) -> Self {
+        let staged = if status.is_conflicted() {
+            GitStatus::Conflicted
+        } else if status.contains(Status::INDEX_NEW) {
+            GitStatus::New
+        } else if status.contains(Status::INDEX_MODIFIED) {
+            GitStatus::Modified
+        } else if status.contains(Status::INDEX_DELETED) {
+            GitStatus::Deleted
+        } else if status.contains(Status::INDEX_RENAMED) {
+            GitStatus::Renamed
+        } else if status.contains(Status::INDEX_TYPECHANGE) {
+            GitStatus::Typechange
+        } else if status.contains(Status::IGNORED) {
+            GitStatus::Ignored
+        } else {
+            GitStatus::Unmodified
+        };
+
+        let unstaged = if status.is_conflicted() {
+            GitStatus::Conflicted
+        } else if status.contains(Status::WT_NEW) {
+            GitStatus::New
+        } else if status.contains(Status::WT_MODIFIED) {
+            GitStatus::Modified
+        } else if status.contains(Status::WT_DELETED) {
+            GitStatus::Deleted
+        } else if status.contains(Status::WT_RENAMED) {
+            GitStatus::Renamed
+        } else if status.contains(Status::WT_TYPECHANGE) {
+            GitStatus::Typechange
+        } else if status.contains(Status::IGNORED) {
+            GitStatus::Ignored
+        } else {
+            GitStatus::Unmodified
+        };
+
+        Self { staged, unstaged }
+    }
+}
+
+/// A per-directory cache of libgit2 statuses.
+///
+/// `Core::fetch` builds one of these for each listed path and reuses it for
+/// every directory beneath it; the enclosing repository is located once by
+/// walking parent directories looking for a `.git`, and its `statuses()` are
+/// materialized into a map keyed by the lexically normalized absolute path of
+/// every entry. Symlinks in those paths are not resolved.
+pub struct GitCache {
+    /// Per-file statuses, keyed by absolute path.
+    files: HashMap<PathBuf, GitFileStatus>,
+    /// Pre-rolled-up directory statuses, keyed by absolute path — computed once
+    /// at build time by walking each file up to the workdir, so render-time
+    /// lookups are O(1) instead of rescanning the whole map per directory.
+    dirs: HashMap<PathBuf, GitFileStatus>,
+    /// The canonical repository workdir; the ceiling for rollup propagation.
+    workdir: PathBuf,
+}
+
+impl GitCache {
+    /// Build the cache for the directory containing `path`, or an empty cache
+    /// when `path` is not inside a repository (or libgit2 errors out).
+    pub fn new(path: &Path) -> Self {
+        let repo = match discover(path) {
+            Some(repo) => repo,
+            None => return Self::empty(),
+        };
+
+        let workdir = match repo.workdir() {
+            Some(workdir) => lexical_normalize(workdir),
+            None => return Self::empty(),
+        };
+
+        let mut options = StatusOptions::new();
+        options
+            .include_untracked(true)
+            .include_ignored(true)
+            .recurse_untracked_dirs(true);
+
+        let statuses = match repo.statuses(Some(&mut options)) {
+            Ok(statuses) => statuses,
+            Err(_) => return Self::empty(),
+        };
+
+        let mut files = HashMap::new();
+        let mut dirs: HashMap<PathBuf, GitFileStatus> = HashMap::new();
+        for entry in statuses.iter() {
+            let rel = match entry.path() {
+                Some(rel) => rel,
+                None => continue,
+            };
+            // Statuses are workdir-relative; join them onto the workdir to form
+            // the absolute key callers look up by, normalized the same way.
+            let abs = lexical_normalize(&workdir.join(rel));
+            let status = GitFileStatus::from_bits(entry.status());
+
+            // Roll the file's status up into every ancestor directory between
+            // it and the workdir, keeping the "worst" status seen so far.
+            let mut ancestor = abs.parent();
+            while let Some(dir) = ancestor {
+                let rollup = dirs.entry(dir.to_path_buf()).or_insert_with(GitFileStatus::clean);
+                rollup.merge_worst(status);
+                if dir == workdir {
+                    break;
+                }
+                ancestor = dir.parent();
+            }
+
+            files.insert(abs, status);
+        }
+
+        Self {
+            files,
+            dirs,
+            workdir,
+        }
+    }
+
+    fn empty() -> Self {
+        Self {
+            files: HashMap::new(),
+            dirs: HashMap::new(),
+            workdir: PathBuf::new(),
+        }
+    }
+
+    /// Normalize a query path to the absolute, lexically-normalized form used
+    /// as a key. Purely lexical — no filesystem access, so lookups stay cheap
+    /// even for the clean files that miss the map.
+    fn key(&self, path: &Path) -> PathBuf {
+        let absolute = if path.is_absolute() {
+            path.to_path_buf()
+        } else {
+            self.workdir.join(path)
+        };
+        lexical_normalize(&absolute)
+    }
+
+    /// The status of a single file, defaulting to clean when unknown.
+    pub fn get(&self, path: &Path) -> GitFileStatus {
+        self.files
+            .get(&self.key(path))
+            .copied()
+            .unwrap_or_else(GitFileStatus::clean)
+    }
+
+    /// A directory's pre-computed "worst" status, defaulting to clean.
+    pub fn get_dir(&self, path: &Path) -> GitFileStatus {
+        self.dirs
+            .get(&self.key(path))
+            .copied()
+            .unwrap_or_else(GitFileStatus::clean)
+    }
+}
+
+/// A repository handle used to answer "would this path be ignored?" for the
+/// `--git-ignore` filter. The repository is discovered and opened once per tree
+/// and reused for every candidate path, mirroring the git-status cache.
+pub struct GitIgnore {
+    repo: Option<Repository>,
+    workdir: PathBuf,
+}
+
+impl GitIgnore {
+    /// Open the repository enclosing `path`, if any. Paths outside a repository
+    /// get a handle that never ignores anything.
+    pub fn new(path: &Path) -> Self {
+        match discover(path).and_then(|repo| {
+            let workdir = repo.workdir().map(lexical_normalize);
+            workdir.map(|workdir| (repo, workdir))
+        }) {
+            Some((repo, workdir)) => Self {
+                repo: Some(repo),
+                workdir,
+            },
+            None => Self {
+                repo: None,
+                workdir: PathBuf::new(),
+            },
+        }
+    }
+
+    /// Whether the enclosing repository would ignore `path`.
+    ///
+    /// Returns `false` (i.e. "show it") as a safe fallback whenever we cannot
+    /// positively determine the answer: a path outside any repository, one that
+    /// does not sit under the repository workdir, or one libgit2 declines to
+    /// classify. Erring toward showing an entry never hides files the user
+    /// expects.
+    pub fn should_ignore(&self, path: &Path) -> bool {
+        let repo = match self.repo {
+            Some(ref repo) => repo,
+            None => return false,
+        };
+        // Normalize the query exactly as `GitCache` keys its statuses — purely
+        // lexical, so a symlinked workdir does not desync the two halves of the
+        // comparison the way resolving one side with `canonicalize` would.
+        let absolute = if path.is_absolute() {
+            path.to_path_buf()
+        } else {
+            self.workdir.join(path)
+        };
+        let abs = lexical_normalize(&absolute);
+        let rel = match abs.strip_prefix(&self.workdir) {
+            Ok(rel) => rel,
+            Err(_) => return false,
+        };
+        repo.status_should_ignore(rel).unwrap_or(false)
+    }
+}
+
+/// Lexically normalize a path: resolve `.` and `..` components without touching
+/// the filesystem, so two spellings of the same path hash to the same key.
+fn lexical_normalize(path: &Path) -> PathBuf {
+    use std::path::Component;
+
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if !normalized.pop() {
+                    normalized.push("..");
+                }
+            }
+            other => normalized.push(other.as_os_str()),
+        }
+    }
+    normalized
+}
+
+/// Walk `path` and its parents looking for a repository to open.
+fn discover(path: &Path) -> Option<Repository> {
+    let mut current = Some(path);
+    while let Some(dir) = current {
+        if dir.join(".git").exists() {
+            if let Ok(repo) = Repository::open(dir) {
+                return Some(repo);
+            }
+        }
+        current = dir.parent();
+    }
+    None
+}
@@ -0,0 +1,111 @@
+use std::collections::HashMap;
+
+use crate::meta::{FileType, Meta};
+use crate::theme::IconConfig;
+
+/// Which compiled-in icon set to start from.
+#[derive(Clone, Debug, Copy, PartialEq, Eq)]
+pub enum Theme {
+    NoIcon,
+    Fancy,
+    Unicode,
+}
+
+/// The resolved icon table: the built-in preset plus any per-extension or
+/// per-filename overrides loaded from the user's config.
+pub struct Icons {
+    display: bool,
+    by_name: HashMap<String, String>,
+    by_extension: HashMap<String, String>,
+    default_file: String,
+    default_dir: String,
+}
+
+impl Icons {
+    /// Build the table from a preset only.
+    pub fn new(theme: Theme) -> Self {
+        Self::with_config(theme, &IconConfig::default())
+    }
+
+    /// Build the table from a preset, layering user overrides on top. The
+    /// `NoIcon` preset suppresses icons entirely regardless of overrides.
+    pub fn with_config(theme: Theme, config: &IconConfig) -> Self {
+        let (display, mut by_name, mut by_extension, default_file, default_dir) = match theme {
+            Theme::NoIcon => (
+                false,
+                HashMap::new(),
+                HashMap::new(),
+                String::new(),
+                String::new(),
+            ),
+            Theme::Fancy => (
+                true,
+                fancy_by_name(),
+                fancy_by_extension(),
+                "\u{f15b}".to_string(), //
+                "\u{f413}".to_string(), //
+            ),
+            Theme::Unicode => (
+                true,
+                HashMap::new(),
+                HashMap::new(),
+                "\u{1f5cb}".to_string(),
+                "\u{1f4c1}".to_string(),
+            ),
+        };
+
+        // User entries supersede individual preset glyphs without discarding
+        // the rest of the preset.
+        by_name.extend(config.by_name.clone());
+        by_extension.extend(config.by_extension.clone());
+
+        Self {
+            display,
+            by_name,
+            by_extension,
+            default_file,
+            default_dir,
+        }
+    }
+
+    /// The icon for `meta`, including a trailing space, or the empty string
+    /// when icons are disabled.
+    pub fn get(&self, meta: &Meta) -> String {
+        if !self.display {
+            return String::new();
+        }
+
+        let icon = if let Some(glyph) = self.by_name.get(&meta.name) {
+            glyph.clone()
+        } else if let Some(glyph) = meta
+            .path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .and_then(|ext| self.by_extension.get(ext))
+        {
+            glyph.clone()
+        } else if meta.file_type == FileType::Directory {
+            self.default_dir.clone()
+        } else {
+            self.default_file.clone()
+        };
+
+        format!("{} ", icon)
+    }
+}
+
+fn fancy_by_name() -> HashMap<String, String> {
+    let mut map = HashMap::new();
+    map.insert(".git".into(), "\u{f1d3}".into()); //
+    map.insert("Cargo.toml".into(), "\u{e7a8}".into()); //
+    map.insert("README.md".into(), "\u{f48a}".into()); //
+    map
+}
+
+fn fancy_by_extension() -> HashMap<String, String> {
+    let mut map = HashMap::new();
+    map.insert("rs".into(), "\u{e7a8}".into()); //
+    map.insert("toml".into(), "\u{e615}".into()); //
+    map.insert("md".into(), "\u{f48a}".into()); //
+    map
+}
@@ -0,0 +1,11 @@
+pub mod app;
+pub mod color;
+pub mod core;
+pub mod display;
+pub mod flags;
+#[cfg(feature = "git")]
+pub mod git;
+pub mod icon;
+pub mod meta;
+pub mod sort;
+pub mod theme;
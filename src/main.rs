@@ -0,0 +1,25 @@
+use std::path::PathBuf;
+use std::process;
+
+use lsd::app;
+use lsd::core::Core;
+use lsd::flags::Flags;
+
+fn main() {
+    let matches = app::build().get_matches();
+
+    let flags = match Flags::from_matches(&matches) {
+        Ok(flags) => flags,
+        Err(err) => err.exit(),
+    };
+
+    let paths: Vec<PathBuf> = matches
+        .values_of("FILE")
+        .expect("FILE has a default value")
+        .map(PathBuf::from)
+        .collect();
+
+    Core::new(flags).run(paths);
+
+    process::exit(0);
+}
@@ -0,0 +1,426 @@
+mod xattr;
+
+pub use xattr::Attributes;
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::flags::{DateField, DateFlag, Display, Flags, SizeFlag};
+
+#[cfg(feature = "git")]
+use crate::git::{GitFileStatus, GitIgnore};
+
+/// The kind of a filesystem entry, used for coloring and the trailing
+/// indicator character.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FileType {
+    Directory,
+    File,
+    SymLink,
+    Pipe,
+    Socket,
+    BlockDevice,
+    CharDevice,
+    Special,
+}
+
+impl FileType {
+    fn from_metadata(meta: &fs::Metadata) -> Self {
+        let ft = meta.file_type();
+        if ft.is_dir() {
+            FileType::Directory
+        } else if ft.is_symlink() {
+            FileType::SymLink
+        } else if ft.is_file() {
+            FileType::File
+        } else {
+            #[cfg(unix)]
+            {
+                use std::os::unix::fs::FileTypeExt;
+                if ft.is_fifo() {
+                    return FileType::Pipe;
+                } else if ft.is_socket() {
+                    return FileType::Socket;
+                } else if ft.is_block_device() {
+                    return FileType::BlockDevice;
+                } else if ft.is_char_device() {
+                    return FileType::CharDevice;
+                }
+            }
+            FileType::Special
+        }
+    }
+
+    /// The leading character of the permission string (`d`, `l`, `-`, …).
+    pub fn as_char(self) -> char {
+        match self {
+            FileType::Directory => 'd',
+            FileType::SymLink => 'l',
+            FileType::File => '-',
+            FileType::Pipe => 'p',
+            FileType::Socket => 's',
+            FileType::BlockDevice => 'b',
+            FileType::CharDevice => 'c',
+            FileType::Special => '?',
+        }
+    }
+}
+
+/// The owning user and group of an entry.
+#[derive(Clone, Debug)]
+pub struct Owner {
+    pub user: String,
+    pub group: String,
+}
+
+impl Owner {
+    #[cfg(unix)]
+    fn from_metadata(meta: &fs::Metadata) -> Self {
+        use std::os::unix::fs::MetadataExt;
+
+        let user = users::get_user_by_uid(meta.uid())
+            .map(|u| u.name().to_string_lossy().into_owned())
+            .unwrap_or_else(|| meta.uid().to_string());
+        let group = users::get_group_by_gid(meta.gid())
+            .map(|g| g.name().to_string_lossy().into_owned())
+            .unwrap_or_else(|| meta.gid().to_string());
+
+        Self { user, group }
+    }
+
+    #[cfg(not(unix))]
+    fn from_metadata(_meta: &fs::Metadata) -> Self {
+        Self {
+            user: "-".into(),
+            group: "-".into(),
+        }
+    }
+}
+
+/// The POSIX permission bits, kept as a raw mode for rendering.
+#[derive(Clone, Copy, Debug)]
+pub struct Permissions {
+    mode: u32,
+    file_type: FileType,
+}
+
+impl Permissions {
+    fn from_metadata(meta: &fs::Metadata, file_type: FileType) -> Self {
+        #[cfg(unix)]
+        let mode = {
+            use std::os::unix::fs::PermissionsExt;
+            meta.permissions().mode()
+        };
+        #[cfg(not(unix))]
+        let mode = if meta.permissions().readonly() {
+            0o444
+        } else {
+            0o644
+        };
+
+        Self { mode, file_type }
+    }
+
+    /// The ten-character `drwxr-xr-x`-style string.
+    pub fn render(&self) -> String {
+        let mut out = String::with_capacity(10);
+        out.push(self.file_type.as_char());
+        let bits = [
+            (0o400, 'r'),
+            (0o200, 'w'),
+            (0o100, 'x'),
+            (0o040, 'r'),
+            (0o020, 'w'),
+            (0o010, 'x'),
+            (0o004, 'r'),
+            (0o002, 'w'),
+            (0o001, 'x'),
+        ];
+        for (mask, ch) in bits.iter() {
+            out.push(if self.mode & mask != 0 { *ch } else { '-' });
+        }
+        out
+    }
+}
+
+/// An entry's size in bytes.
+#[derive(Clone, Copy, Debug)]
+pub struct Size {
+    bytes: u64,
+}
+
+impl Size {
+    fn new(bytes: u64) -> Self {
+        Self { bytes }
+    }
+
+    pub fn bytes(self) -> u64 {
+        self.bytes
+    }
+
+    /// Human-readable size honoring the configured `SizeFlag`.
+    pub fn render(self, flag: SizeFlag) -> String {
+        match flag {
+            SizeFlag::Bytes => self.bytes.to_string(),
+            SizeFlag::Short | SizeFlag::Default => {
+                let units = ["B", "KB", "MB", "GB", "TB"];
+                let mut value = self.bytes as f64;
+                let mut unit = 0;
+                while value >= 1024.0 && unit < units.len() - 1 {
+                    value /= 1024.0;
+                    unit += 1;
+                }
+                if unit == 0 {
+                    format!("{}{}", self.bytes, units[unit])
+                } else {
+                    format!("{:.1}{}", value, units[unit])
+                }
+            }
+        }
+    }
+}
+
+/// The three timestamps `lsd` can sort on and render: modified, accessed and
+/// changed (ctime). They are captured once at `from_path` time.
+#[derive(Clone, Copy, Debug)]
+pub struct Dates {
+    pub modified: SystemTime,
+    pub accessed: SystemTime,
+    pub changed: SystemTime,
+}
+
+impl Dates {
+    fn from_metadata(meta: &fs::Metadata) -> Self {
+        let modified = meta.modified().unwrap_or(UNIX_EPOCH);
+        let accessed = meta.accessed().unwrap_or(UNIX_EPOCH);
+
+        #[cfg(unix)]
+        let changed = {
+            use std::os::unix::fs::MetadataExt;
+            let secs = meta.ctime();
+            if secs >= 0 {
+                UNIX_EPOCH + Duration::from_secs(secs as u64)
+            } else {
+                UNIX_EPOCH
+            }
+        };
+        #[cfg(not(unix))]
+        let changed = modified;
+
+        Self {
+            modified,
+            accessed,
+            changed,
+        }
+    }
+
+    /// The timestamp selected by `DateField`.
+    pub fn select(&self, field: DateField) -> SystemTime {
+        match field {
+            DateField::Modified => self.modified,
+            DateField::Accessed => self.accessed,
+            DateField::Changed => self.changed,
+        }
+    }
+
+    /// Render the field named by `flag`, either absolutely or relatively.
+    pub fn render(&self, flag: DateFlag) -> String {
+        let time = self.select(flag.field);
+        if flag.relative {
+            render_relative(time)
+        } else {
+            render_absolute(time)
+        }
+    }
+}
+
+fn render_absolute(time: SystemTime) -> String {
+    use chrono::{DateTime, Local};
+    let datetime: DateTime<Local> = time.into();
+    datetime.format("%d %b %H:%M").to_string()
+}
+
+fn render_relative(time: SystemTime) -> String {
+    let elapsed = match SystemTime::now().duration_since(time) {
+        Ok(elapsed) => elapsed.as_secs(),
+        Err(_) => return "in the future".to_string(),
+    };
+    let (value, unit) = if elapsed < 60 {
+        (elapsed, "second")
+    } else if elapsed < 3600 {
+        (elapsed / 60, "minute")
+    } else if elapsed < 86400 {
+        (elapsed / 3600, "hour")
+    } else {
+        (elapsed / 86400, "day")
+    };
+    if value == 1 {
+        format!("1 {} ago", unit)
+    } else {
+        format!("{} {}s ago", value, unit)
+    }
+}
+
+/// A single filesystem entry plus, for directories, its recursed contents.
+#[derive(Clone, Debug)]
+pub struct Meta {
+    pub path: PathBuf,
+    pub name: String,
+    pub file_type: FileType,
+    pub permissions: Permissions,
+    pub owner: Owner,
+    pub size: Size,
+    pub dates: Dates,
+    pub symlink_target: Option<PathBuf>,
+    pub xattrs: Attributes,
+    pub content: Option<Vec<Meta>>,
+    #[cfg(feature = "git")]
+    pub git_status: Option<GitFileStatus>,
+}
+
+impl Meta {
+    /// Stat `path` and capture everything the renderer needs. Symlinks report
+    /// the target's metadata unless `--no-symlink` is set.
+    pub fn from_path(path: &Path, flags: &Flags) -> io::Result<Self> {
+        let link_meta = fs::symlink_metadata(path)?;
+        let is_symlink = link_meta.file_type().is_symlink();
+        let follow_symlink = is_symlink && !flags.no_symlink;
+
+        // Follow the link to the target when we can and are allowed to;
+        // fall back to the link node itself (e.g. a broken link).
+        let stat = if follow_symlink {
+            fs::metadata(path).unwrap_or(link_meta.clone())
+        } else {
+            link_meta.clone()
+        };
+
+        let symlink_target = if is_symlink {
+            fs::read_link(path).ok()
+        } else {
+            None
+        };
+
+        let file_type = if is_symlink {
+            FileType::SymLink
+        } else {
+            FileType::from_metadata(&stat)
+        };
+
+        let name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_else(|| path.to_string_lossy().into_owned());
+
+        Ok(Self {
+            name,
+            permissions: Permissions::from_metadata(&stat, file_type),
+            owner: Owner::from_metadata(&stat),
+            size: Size::new(stat.len()),
+            dates: Dates::from_metadata(&stat),
+            xattrs: Attributes::from_path(path, follow_symlink),
+            symlink_target,
+            file_type,
+            content: None,
+            #[cfg(feature = "git")]
+            git_status: None,
+            path: path.to_path_buf(),
+        })
+    }
+
+    /// Read a directory's children honoring the `Display` visibility rules and,
+    /// when `--git-ignore` is active, skipping paths the enclosing repository
+    /// ignores *before* recursing into them.
+    #[cfg(feature = "git")]
+    pub fn recurse_into(
+        &mut self,
+        depth: usize,
+        flags: &Flags,
+        ignore: Option<&GitIgnore>,
+    ) -> io::Result<Option<Vec<Meta>>> {
+        self.recurse_impl(depth, flags, ignore)
+    }
+
+    #[cfg(not(feature = "git"))]
+    pub fn recurse_into(
+        &mut self,
+        depth: usize,
+        flags: &Flags,
+    ) -> io::Result<Option<Vec<Meta>>> {
+        self.recurse_impl(depth, flags)
+    }
+
+    fn recurse_impl(
+        &mut self,
+        depth: usize,
+        flags: &Flags,
+        #[cfg(feature = "git")] ignore: Option<&GitIgnore>,
+    ) -> io::Result<Option<Vec<Meta>>> {
+        if depth == 0 || self.file_type != FileType::Directory {
+            return Ok(None);
+        }
+
+        let mut content = Vec::new();
+        for entry in fs::read_dir(&self.path)? {
+            let entry = entry?;
+            let path = entry.path();
+
+            let name = entry.file_name();
+            let name = name.to_string_lossy();
+            if !is_visible(&name, flags.display) {
+                continue;
+            }
+
+            // The git-ignore filter runs before recursion so ignored
+            // directories are never descended into. It composes with (rather
+            // than replaces) the visibility rules checked above.
+            #[cfg(feature = "git")]
+            {
+                if let Some(ignore) = ignore {
+                    if ignore.should_ignore(&path) {
+                        continue;
+                    }
+                }
+            }
+
+            let mut child = match Meta::from_path(&path, flags) {
+                Ok(child) => child,
+                Err(_) => continue,
+            };
+
+            #[cfg(feature = "git")]
+            {
+                child.content = child.recurse_impl(depth - 1, flags, ignore)?;
+            }
+            #[cfg(not(feature = "git"))]
+            {
+                child.content = child.recurse_impl(depth - 1, flags)?;
+            }
+
+            content.push(child);
+        }
+
+        Ok(Some(content))
+    }
+
+    /// Sum this entry's size with everything beneath it.
+    pub fn calculate_total_size(&mut self) {
+        if let Some(ref mut content) = self.content {
+            let mut total = self.size.bytes();
+            for child in content.iter_mut() {
+                child.calculate_total_size();
+                total += child.size.bytes();
+            }
+            self.size = Size::new(total);
+        }
+    }
+}
+
+fn is_visible(name: &str, display: Display) -> bool {
+    match display {
+        Display::DisplayAll | Display::DisplayDirectoryItself => true,
+        Display::DisplayAlmostAll => name != "." && name != "..",
+        Display::DisplayOnlyVisible => !name.starts_with('.'),
+    }
+}
@@ -0,0 +1,66 @@
+use std::path::Path;
+
+/// A single extended attribute: its name and the byte-length of its value.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Attribute {
+    pub name: String,
+    pub size: usize,
+}
+
+/// The extended attributes attached to a file.
+///
+/// Probing never errors: a file with no attributes, or a filesystem/platform
+/// that does not support them, simply yields an empty list so that `Core::fetch`
+/// can query it unconditionally.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Attributes {
+    attributes: Vec<Attribute>,
+}
+
+impl Attributes {
+    /// Probe `path` for extended attributes, following symlinks unless
+    /// `follow_symlink` is `false` (i.e. `--no-symlink`).
+    #[cfg(unix)]
+    pub fn from_path(path: &Path, follow_symlink: bool) -> Self {
+        let names = if follow_symlink {
+            xattr::list_deref(path)
+        } else {
+            xattr::list(path)
+        };
+
+        let attributes = match names {
+            Ok(names) => names
+                .map(|name| {
+                    let value = if follow_symlink {
+                        xattr::get_deref(path, &name)
+                    } else {
+                        xattr::get(path, &name)
+                    };
+                    let size = value.ok().flatten().map(|v| v.len()).unwrap_or(0);
+                    Attribute {
+                        name: name.to_string_lossy().into_owned(),
+                        size,
+                    }
+                })
+                .collect(),
+            Err(_) => Vec::new(),
+        };
+
+        Self { attributes }
+    }
+
+    #[cfg(not(unix))]
+    pub fn from_path(_path: &Path, _follow_symlink: bool) -> Self {
+        Self::default()
+    }
+
+    /// Whether the file carries any extended attributes, used to decide whether
+    /// to append the `@` indicator after the permission string.
+    pub fn has_attributes(&self) -> bool {
+        !self.attributes.is_empty()
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, Attribute> {
+        self.attributes.iter()
+    }
+}
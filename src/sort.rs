@@ -0,0 +1,52 @@
+use std::cmp::Ordering;
+
+use crate::flags::{DirOrderFlag, Flags, SortFlag, SortOrder};
+use crate::meta::{FileType, Meta};
+
+/// Total ordering of two entries under the active sort/order/group flags.
+pub fn by_meta(a: &Meta, b: &Meta, flags: &Flags) -> Ordering {
+    // Directory grouping wins over the field comparison, like coreutils
+    // `--group-directories-first`.
+    match flags.directory_order {
+        DirOrderFlag::First => {
+            if let Some(order) = group_dirs(a, b, true) {
+                return order;
+            }
+        }
+        DirOrderFlag::Last => {
+            if let Some(order) = group_dirs(a, b, false) {
+                return order;
+            }
+        }
+        DirOrderFlag::None => {}
+    }
+
+    let ordering = match flags.sort_by {
+        SortFlag::Name => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
+        SortFlag::Size => b.size.bytes().cmp(&a.size.bytes()),
+        SortFlag::Time => b.dates.modified.cmp(&a.dates.modified),
+        SortFlag::ChangeTime => b.dates.changed.cmp(&a.dates.changed),
+        SortFlag::AccessTime => b.dates.accessed.cmp(&a.dates.accessed),
+    };
+
+    match flags.sort_order {
+        SortOrder::Default => ordering,
+        SortOrder::Reverse => ordering.reverse(),
+    }
+}
+
+/// Order directories before (or after) files, or `None` when both sides are
+/// the same kind and the field comparison should decide.
+fn group_dirs(a: &Meta, b: &Meta, dirs_first: bool) -> Option<Ordering> {
+    let a_dir = a.file_type == FileType::Directory;
+    let b_dir = b.file_type == FileType::Directory;
+    if a_dir == b_dir {
+        return None;
+    }
+    let dir_side = if dirs_first {
+        Ordering::Less
+    } else {
+        Ordering::Greater
+    };
+    Some(if a_dir { dir_side } else { dir_side.reverse() })
+}
@@ -0,0 +1,165 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+/// A data-driven description of the color and icon tables, loaded from the
+/// user's config directory and layered on top of the compiled-in presets.
+///
+/// Every field is optional: a missing file, or a missing key within a present
+/// file, falls back to the built-in default so that existing behavior is
+/// unchanged when no configuration exists.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct ThemeConfig {
+    pub colors: ColorConfig,
+    pub icons: IconConfig,
+}
+
+/// Overrides for the element colors. Names are parsed by the `color` module.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct ColorConfig {
+    pub permission: Option<String>,
+    pub user: Option<String>,
+    pub group: Option<String>,
+    pub size: Option<String>,
+    pub date: Option<String>,
+    pub directory: Option<String>,
+    pub symlink: Option<String>,
+    pub broken_symlink: Option<String>,
+}
+
+/// Overrides for the icon table. `by_extension`/`by_name` supersede individual
+/// entries of the built-in `Fancy`/`Unicode` presets without replacing them.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct IconConfig {
+    pub by_extension: HashMap<String, String>,
+    pub by_name: HashMap<String, String>,
+}
+
+impl ThemeConfig {
+    /// Build the effective theme: start from `LS_COLORS` so we stay at parity
+    /// with coreutils `ls --color`, then layer the config files on top so that
+    /// explicit user config always wins. Missing env, files, or keys leave the
+    /// compiled-in defaults untouched.
+    pub fn load(explicit: Option<&Path>) -> Self {
+        let mut config = Self::default();
+        config.colors.seed_from_ls_colors();
+
+        let dir = match resolve_config_dir(explicit) {
+            Some(dir) => dir,
+            None => return config,
+        };
+
+        // `--config`/`--theme` may point at either the config directory or a
+        // single combined config file; a non-directory path is loaded as one
+        // file so pointing `--config` at a file no longer silently no-ops.
+        if dir.is_file() {
+            if let Some(loaded) = read_config::<ThemeConfig>(&dir) {
+                config.merge(loaded);
+            }
+            return config;
+        }
+
+        if let Some(colors) = read_first::<ColorConfig>(&dir, "colors") {
+            config.colors.merge(colors);
+        }
+        if let Some(icons) = read_first::<IconConfig>(&dir, "icons") {
+            config.icons.merge(icons);
+        }
+        config
+    }
+
+    fn merge(&mut self, other: ThemeConfig) {
+        self.colors.merge(other.colors);
+        self.icons.merge(other.icons);
+    }
+}
+
+impl ColorConfig {
+    /// Overlay any set fields of `other` onto `self`.
+    fn merge(&mut self, other: ColorConfig) {
+        macro_rules! take {
+            ($field:ident) => {
+                if other.$field.is_some() {
+                    self.$field = other.$field;
+                }
+            };
+        }
+        take!(permission);
+        take!(user);
+        take!(group);
+        take!(size);
+        take!(date);
+        take!(directory);
+        take!(symlink);
+        take!(broken_symlink);
+    }
+
+    /// Seed the directory/symlink/broken-link colors from the `LS_COLORS`
+    /// environment variable, matching coreutils `ls --color` for the keys we
+    /// surface. Only fills fields the user has not otherwise configured.
+    fn seed_from_ls_colors(&mut self) {
+        let ls_colors = match std::env::var("LS_COLORS") {
+            Ok(value) => value,
+            Err(_) => return,
+        };
+
+        for entry in ls_colors.split(':') {
+            let mut parts = entry.splitn(2, '=');
+            let key = parts.next().unwrap_or("");
+            let value = match parts.next() {
+                Some(value) if !value.is_empty() => value.to_string(),
+                _ => continue,
+            };
+            match key {
+                "di" => self.directory.get_or_insert(value),
+                "ln" => self.symlink.get_or_insert(value),
+                "or" => self.broken_symlink.get_or_insert(value),
+                _ => continue,
+            };
+        }
+    }
+}
+
+impl IconConfig {
+    /// Overlay `other`'s entries, letting later definitions win per key.
+    fn merge(&mut self, other: IconConfig) {
+        self.by_extension.extend(other.by_extension);
+        self.by_name.extend(other.by_name);
+    }
+}
+
+/// Resolve the directory (or file) holding the theme config.
+///
+/// An explicit `--config`/`--theme` path wins; otherwise fall back to
+/// `$XDG_CONFIG_HOME/lsd` (or `~/.config/lsd`). Returns `None` when no
+/// location can be determined, so the caller keeps the compiled-in defaults.
+pub fn resolve_config_dir(explicit: Option<&Path>) -> Option<PathBuf> {
+    if let Some(path) = explicit {
+        return Some(path.to_path_buf());
+    }
+
+    if let Some(xdg) = std::env::var_os("XDG_CONFIG_HOME") {
+        return Some(PathBuf::from(xdg).join("lsd"));
+    }
+
+    std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config").join("lsd"))
+}
+
+/// Read a `basename.yaml` or `basename.toml` from `dir`, preferring YAML.
+fn read_first<T: for<'de> Deserialize<'de>>(dir: &Path, basename: &str) -> Option<T> {
+    read_config(&dir.join(format!("{}.yaml", basename)))
+        .or_else(|| read_config(&dir.join(format!("{}.toml", basename))))
+}
+
+/// Parse a single config file, selecting the format from its extension.
+fn read_config<T: for<'de> Deserialize<'de>>(path: &Path) -> Option<T> {
+    let contents = std::fs::read_to_string(path).ok()?;
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some("toml") => toml::from_str(&contents).ok(),
+        _ => serde_yaml::from_str(&contents).ok(),
+    }
+}